@@ -3,19 +3,19 @@
 //! It provides functionality similar to that of the `ebsnvme-id` command but adds
 //! information about instance store devices, not only EBS.
 //!
-//! The library implements [`TryFrom<File>`] for [`Nvme`], to use as the constructor.
+//! The library implements [`TryFrom<File>`] for [`Nvme`], to use as the constructor,
+//! along with [`Nvme::from_path`] for opening a device by path directly.
 //!
 //! # Example
 //!
 //! ```
-//! use std::fs::File;
+//! use std::env::args;
 //!
 //! use nvme_amz::Nvme;
 //!
 //! fn main() {
 //!     let path = args().nth(1).expect("device path required");
-//!     let file = File::open(path).expect("unable to open device");
-//!     let nvme: Nvme = file.try_into().expect("unable to probe device");
+//!     let nvme = Nvme::from_path(path).expect("unable to probe device");
 //!     println!("{:?}", nvme);
 //!     let name = nvme.name();
 //!     println!("name: {}", name);
@@ -26,6 +26,8 @@ use std::ffi::{c_char, c_uchar, c_uint, c_ulonglong, c_ushort};
 #[cfg(any(feature = "ioctl-nix", feature = "ioctl-rustix"))]
 use std::fs::File;
 use std::os::fd::AsFd;
+#[cfg(any(feature = "ioctl-nix", feature = "ioctl-rustix"))]
+use std::path::{Path, PathBuf};
 use std::{fmt, io};
 
 const AMZ_EBS_MN: &str = "Amazon Elastic Block Store";
@@ -33,8 +35,15 @@ const AMZ_INST_STORE_MN: &str = "Amazon EC2 NVMe Instance Storage";
 const AMZ_VENDOR_ID: c_ushort = 0x1D0F;
 
 const NVME_ADMIN_IDENTIFY: u8 = 0x06;
+const NVME_ADMIN_GET_LOG_PAGE: u8 = 0x02;
 const NVME_IOCTL_ADMIN_CMD_NUM: u8 = 0x41;
 
+const NVME_LOG_SMART: u32 = 0x02;
+const NVME_LOG_SMART_NUM_DWORDS: u32 = 128;
+
+/// The namespace id most EC2 NVMe devices expose as their sole namespace.
+pub const NVME_DEFAULT_NSID: u32 = 1;
+
 /// The error type for this crate.
 #[derive(Debug)]
 pub enum Error {
@@ -358,6 +367,152 @@ impl Default for NvmeIdCtrl {
     }
 }
 
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct NvmeSmartLogPage {
+    critical_warning: c_uchar,
+    composite_temp: [c_uchar; 2],
+    avail_spare: c_uchar,
+    spare_thresh: c_uchar,
+    percent_used: c_uchar,
+    rsvd6: [c_uchar; 26],
+    data_units_read: [c_uchar; 16],
+    data_units_written: [c_uchar; 16],
+    host_read_commands: [c_uchar; 16],
+    host_write_commands: [c_uchar; 16],
+    controller_busy_time: [c_uchar; 16],
+    power_cycles: [c_uchar; 16],
+    power_on_hours: [c_uchar; 16],
+    unsafe_shutdowns: [c_uchar; 16],
+    media_errors: [c_uchar; 16],
+    num_err_log_entries: [c_uchar; 16],
+    rsvd192: [c_uchar; 320],
+}
+
+impl Default for NvmeSmartLogPage {
+    fn default() -> Self {
+        Self {
+            critical_warning: 0,
+            composite_temp: [0; 2],
+            avail_spare: 0,
+            spare_thresh: 0,
+            percent_used: 0,
+            rsvd6: [0; 26],
+            data_units_read: [0; 16],
+            data_units_written: [0; 16],
+            host_read_commands: [0; 16],
+            host_write_commands: [0; 16],
+            controller_busy_time: [0; 16],
+            power_cycles: [0; 16],
+            power_on_hours: [0; 16],
+            unsafe_shutdowns: [0; 16],
+            media_errors: [0; 16],
+            num_err_log_entries: [0; 16],
+            rsvd192: [0; 320],
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy)]
+struct NvmeLbaf {
+    ms: c_ushort,
+    lbads: c_uchar,
+    rp: c_uchar,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct NvmeIdNs {
+    nsze: c_ulonglong,
+    ncap: c_ulonglong,
+    nuse: c_ulonglong,
+    nsfeat: c_uchar,
+    nlbaf: c_uchar,
+    flbas: c_uchar,
+    mc: c_uchar,
+    dpc: c_uchar,
+    dps: c_uchar,
+    nmic: c_uchar,
+    rescap: c_uchar,
+    fpi: c_uchar,
+    dlfeat: c_uchar,
+    nawun: c_ushort,
+    nawupf: c_ushort,
+    nacwu: c_ushort,
+    nabsn: c_ushort,
+    nabo: c_ushort,
+    nabspf: c_ushort,
+    noiob: c_ushort,
+    nvmcap: [c_uchar; 16],
+    npwg: c_ushort,
+    npwa: c_ushort,
+    npdg: c_ushort,
+    npda: c_ushort,
+    nows: c_ushort,
+    mssrl: c_ushort,
+    mcl: c_uint,
+    msrc: c_uchar,
+    rsvd81: [c_uchar; 11],
+    anagrpid: c_uint,
+    rsvd96: [c_uchar; 3],
+    nsattr: c_uchar,
+    nvmsetid: c_ushort,
+    endgid: c_ushort,
+    nguid: [c_uchar; 16],
+    eui64: [c_uchar; 8],
+    lbaf: [NvmeLbaf; 16],
+    rsvd192: [c_uchar; 192],
+    vs: [c_uchar; 3712],
+}
+
+impl Default for NvmeIdNs {
+    fn default() -> Self {
+        Self {
+            nsze: 0,
+            ncap: 0,
+            nuse: 0,
+            nsfeat: 0,
+            nlbaf: 0,
+            flbas: 0,
+            mc: 0,
+            dpc: 0,
+            dps: 0,
+            nmic: 0,
+            rescap: 0,
+            fpi: 0,
+            dlfeat: 0,
+            nawun: 0,
+            nawupf: 0,
+            nacwu: 0,
+            nabsn: 0,
+            nabo: 0,
+            nabspf: 0,
+            noiob: 0,
+            nvmcap: [0; 16],
+            npwg: 0,
+            npwa: 0,
+            npdg: 0,
+            npda: 0,
+            nows: 0,
+            mssrl: 0,
+            mcl: 0,
+            msrc: 0,
+            rsvd81: [0; 11],
+            anagrpid: 0,
+            rsvd96: [0; 3],
+            nsattr: 0,
+            nvmsetid: 0,
+            endgid: 0,
+            nguid: [0; 16],
+            eui64: [0; 8],
+            lbaf: [NvmeLbaf::default(); 16],
+            rsvd192: [0; 192],
+            vs: [0; 3712],
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Default, Clone, Copy)]
 struct NvmePassthruCmd {
@@ -411,6 +566,50 @@ mod ioctl_nix {
         unsafe { nvme_identify_ctrl_inner(fd.as_fd().as_raw_fd(), nvme_admin_cmd_ptr) }?;
         Ok(out)
     }
+
+    pub(super) fn nvme_get_log_page<F: AsFd>(fd: F) -> Result<NvmeSmartLogPage> {
+        ioctl_readwrite!(
+            nvme_get_log_page_inner,
+            b'N',
+            NVME_IOCTL_ADMIN_CMD_NUM,
+            NvmeAdminCmd
+        );
+        let mut out = NvmeSmartLogPage::default();
+        let out_ptr = &mut out as *mut _;
+        let mut nvme_admin_cmd = NvmeAdminCmd {
+            addr: out_ptr as c_ulonglong,
+            nsid: u32::MAX,
+            cdw10: NVME_LOG_SMART | ((NVME_LOG_SMART_NUM_DWORDS - 1) << 16),
+            data_len: std::mem::size_of::<NvmeSmartLogPage>() as c_uint,
+            opcode: NVME_ADMIN_GET_LOG_PAGE,
+            ..Default::default()
+        };
+        let nvme_admin_cmd_ptr = &mut nvme_admin_cmd as *mut _;
+        unsafe { nvme_get_log_page_inner(fd.as_fd().as_raw_fd(), nvme_admin_cmd_ptr) }?;
+        Ok(out)
+    }
+
+    pub(super) fn nvme_identify_ns<F: AsFd>(fd: F, nsid: u32) -> Result<NvmeIdNs> {
+        ioctl_readwrite!(
+            nvme_identify_ns_inner,
+            b'N',
+            NVME_IOCTL_ADMIN_CMD_NUM,
+            NvmeAdminCmd
+        );
+        let mut out = NvmeIdNs::default();
+        let out_ptr = &mut out as *mut _;
+        let mut nvme_admin_cmd = NvmeAdminCmd {
+            addr: out_ptr as c_ulonglong,
+            nsid,
+            cdw10: 0,
+            data_len: std::mem::size_of::<NvmeIdNs>() as c_uint,
+            opcode: NVME_ADMIN_IDENTIFY,
+            ..Default::default()
+        };
+        let nvme_admin_cmd_ptr = &mut nvme_admin_cmd as *mut _;
+        unsafe { nvme_identify_ns_inner(fd.as_fd().as_raw_fd(), nvme_admin_cmd_ptr) }?;
+        Ok(out)
+    }
 }
 
 #[cfg(feature = "ioctl-rustix")]
@@ -461,9 +660,94 @@ mod ioctl_rustix {
         let output = unsafe { ioctl(fd, nvme_admin_cmd) }?;
         Ok(output)
     }
+
+    struct NvmeGetLogPageCmd(NvmeAdminCmd);
+
+    unsafe impl Ioctl for NvmeGetLogPageCmd {
+        type Output = NvmeSmartLogPage;
+
+        const IS_MUTATING: bool = false;
+        const OPCODE: Opcode = Opcode::from_components(
+            Direction::ReadWrite,
+            b'N',
+            NVME_IOCTL_ADMIN_CMD_NUM,
+            std::mem::size_of::<NvmeAdminCmd>(),
+        );
+
+        fn as_ptr(&mut self) -> *mut c_void {
+            &mut self.0 as *mut _ as *mut _
+        }
+
+        unsafe fn output_from_ptr(ret: IoctlOutput, ptr: *mut c_void) -> io::Result<Self::Output> {
+            if ret != 0 {
+                return Err(io::Errno::from_raw_os_error(ret));
+            }
+            let sellf = ptr.cast::<NvmeAdminCmd>().read();
+            let data_ptr = sellf.addr as *const NvmeSmartLogPage;
+            let output = data_ptr.cast::<NvmeSmartLogPage>().read();
+            Ok(output)
+        }
+    }
+
+    pub(super) fn nvme_get_log_page<F: AsFd>(fd: F) -> Result<NvmeSmartLogPage> {
+        let mut data = NvmeSmartLogPage::default();
+        let nvme_admin_cmd = NvmeAdminCmd {
+            addr: &mut data as *mut _ as c_ulonglong,
+            nsid: u32::MAX,
+            cdw10: NVME_LOG_SMART | ((NVME_LOG_SMART_NUM_DWORDS - 1) << 16),
+            data_len: std::mem::size_of::<NvmeSmartLogPage>() as c_uint,
+            opcode: NVME_ADMIN_GET_LOG_PAGE,
+            ..Default::default()
+        };
+        let output = unsafe { ioctl(fd, NvmeGetLogPageCmd(nvme_admin_cmd)) }?;
+        Ok(output)
+    }
+
+    struct NvmeIdentifyNsCmd(NvmeAdminCmd);
+
+    unsafe impl Ioctl for NvmeIdentifyNsCmd {
+        type Output = NvmeIdNs;
+
+        const IS_MUTATING: bool = false;
+        const OPCODE: Opcode = Opcode::from_components(
+            Direction::ReadWrite,
+            b'N',
+            NVME_IOCTL_ADMIN_CMD_NUM,
+            std::mem::size_of::<NvmeAdminCmd>(),
+        );
+
+        fn as_ptr(&mut self) -> *mut c_void {
+            &mut self.0 as *mut _ as *mut _
+        }
+
+        unsafe fn output_from_ptr(ret: IoctlOutput, ptr: *mut c_void) -> io::Result<Self::Output> {
+            if ret != 0 {
+                return Err(io::Errno::from_raw_os_error(ret));
+            }
+            let sellf = ptr.cast::<NvmeAdminCmd>().read();
+            let data_ptr = sellf.addr as *const NvmeIdNs;
+            let output = data_ptr.cast::<NvmeIdNs>().read();
+            Ok(output)
+        }
+    }
+
+    pub(super) fn nvme_identify_ns<F: AsFd>(fd: F, nsid: u32) -> Result<NvmeIdNs> {
+        let mut data = NvmeIdNs::default();
+        let nvme_admin_cmd = NvmeAdminCmd {
+            addr: &mut data as *mut _ as c_ulonglong,
+            nsid,
+            cdw10: 0,
+            data_len: std::mem::size_of::<NvmeIdNs>() as c_uint,
+            opcode: NVME_ADMIN_IDENTIFY,
+            ..Default::default()
+        };
+        let output = unsafe { ioctl(fd, NvmeIdentifyNsCmd(nvme_admin_cmd)) }?;
+        Ok(output)
+    }
 }
 
 /// A structure containing vendor-specific device names.
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
 pub struct Names {
     /// Device name defined in the block device mapping.
     pub device_name: Option<String>,
@@ -472,6 +756,7 @@ pub struct Names {
 
     // Force internal creation so the name() method cannot panic, by ensuring
     // either device_name or virtual_name have Some(value).
+    #[cfg_attr(feature = "serialize", serde(skip))]
     _internal: (),
 }
 
@@ -558,6 +843,7 @@ impl TryFrom<&[c_uchar]> for Names {
 
 /// The model of the NVMe device.
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum Model {
     /// Elastic Block Store volume.
     AmazonElasticBlockStore,
@@ -567,10 +853,13 @@ pub enum Model {
 
 /// The vendor ID of the NVMe device.
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(transparent))]
 pub struct VendorId(pub u16);
 
 /// An NVMe device, containing a subset of all identifying information.
 #[derive(Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
 pub struct Nvme {
     /// The [model](Model) of the device.
     pub model: Model,
@@ -578,6 +867,70 @@ pub struct Nvme {
     pub names: Names,
     /// The [vendor ID](VendorId) of the device.
     pub vendor_id: VendorId,
+    /// The firmware revision of the device.
+    pub firmware: String,
+    /// The serial number of the device.
+    pub serial: String,
+}
+
+/// SMART / health information for an NVMe device, as reported by the
+/// Get Log Page command for the SMART/Health Information log.
+#[derive(Debug)]
+pub struct SmartLog {
+    /// Critical warning bitmask.
+    pub critical_warning: u8,
+    /// Composite temperature, in degrees Celsius.
+    pub temperature: i32,
+    /// Available spare capacity, as a percentage of the total spare capacity.
+    pub avail_spare: u8,
+    /// Available spare capacity threshold, as a percentage.
+    pub spare_thresh: u8,
+    /// Percentage of the device's rated endurance consumed.
+    pub percent_used: u8,
+    /// Number of 512-byte data units read, in units of 1000 * 512 bytes.
+    pub data_units_read: u128,
+    /// Number of 512-byte data units written, in units of 1000 * 512 bytes.
+    pub data_units_written: u128,
+    /// Number of host read commands issued.
+    pub host_read_commands: u128,
+    /// Number of host write commands issued.
+    pub host_write_commands: u128,
+    /// Number of power-on hours.
+    pub power_on_hours: u128,
+    /// Number of power cycles.
+    pub power_cycles: u128,
+    /// Number of unsafe shutdowns.
+    pub unsafe_shutdowns: u128,
+    /// Number of occurrences of unrecovered data integrity errors.
+    pub media_errors: u128,
+}
+
+impl From<NvmeSmartLogPage> for SmartLog {
+    fn from(raw: NvmeSmartLogPage) -> Self {
+        Self {
+            critical_warning: raw.critical_warning,
+            temperature: u16::from_le_bytes(raw.composite_temp) as i32 - 273,
+            avail_spare: raw.avail_spare,
+            spare_thresh: raw.spare_thresh,
+            percent_used: raw.percent_used,
+            data_units_read: u128::from_le_bytes(raw.data_units_read),
+            data_units_written: u128::from_le_bytes(raw.data_units_written),
+            host_read_commands: u128::from_le_bytes(raw.host_read_commands),
+            host_write_commands: u128::from_le_bytes(raw.host_write_commands),
+            power_on_hours: u128::from_le_bytes(raw.power_on_hours),
+            power_cycles: u128::from_le_bytes(raw.power_cycles),
+            unsafe_shutdowns: u128::from_le_bytes(raw.unsafe_shutdowns),
+            media_errors: u128::from_le_bytes(raw.media_errors),
+        }
+    }
+}
+
+/// Decode a fixed-size ASCII field such as `fr` or `sn`, trimming trailing
+/// NULs and spaces the way the Identify Controller response pads them.
+fn trim_padded_ascii(chars: &[c_char]) -> String {
+    let mut s = String::from_iter(chars.iter().map(|c| *c as u8 as char));
+    s.truncate(s.trim_end_matches(['\0', ' ']).len());
+    s
 }
 
 impl Nvme {
@@ -590,6 +943,13 @@ impl Nvme {
             .unwrap_or_else(|| self.names.virtual_name.as_ref().unwrap())
     }
 
+    /// Issue a Get Log Page command for the SMART/Health Information log and
+    /// return the decoded result.
+    #[cfg(any(feature = "ioctl-nix", feature = "ioctl-rustix"))]
+    pub fn smart_log<F: AsFd>(&self, fd: F) -> Result<SmartLog> {
+        smart_log(fd)
+    }
+
     fn from_fd<F, IoctlFn>(fd: F, f: IoctlFn) -> Result<Self>
     where
         F: AsFd,
@@ -607,10 +967,14 @@ impl Nvme {
             _ => return Err(Error::UnrecognizedModel(model_str)),
         };
         let names = ctrl.vs.bdev.as_slice().try_into()?;
+        let firmware = trim_padded_ascii(&ctrl.fr);
+        let serial = trim_padded_ascii(&ctrl.sn);
         Ok(Self {
             model,
             names,
             vendor_id: VendorId(ctrl.vid),
+            firmware,
+            serial,
         })
     }
 }
@@ -630,5 +994,291 @@ impl TryFrom<File> for Nvme {
     }
 }
 
+/// Capacity information for an NVMe namespace, as reported by the Identify
+/// Namespace admin command.
+#[derive(Debug)]
+pub struct NvmeNamespace {
+    /// Namespace size, in logical blocks.
+    pub nsze: u64,
+    /// Namespace capacity, in logical blocks.
+    pub ncap: u64,
+    /// Namespace utilization, in logical blocks.
+    pub nuse: u64,
+    /// Size of a single logical block, in bytes, per the namespace's active
+    /// LBA format.
+    pub lba_data_size: u32,
+}
+
+impl NvmeNamespace {
+    /// The usable capacity of the namespace, in bytes.
+    pub fn size_bytes(&self) -> u64 {
+        self.nsze * self.lba_data_size as u64
+    }
+}
+
+impl From<NvmeIdNs> for NvmeNamespace {
+    fn from(raw: NvmeIdNs) -> Self {
+        let lbaf = raw.lbaf[(raw.flbas & 0xf) as usize];
+        Self {
+            nsze: raw.nsze,
+            ncap: raw.ncap,
+            nuse: raw.nuse,
+            lba_data_size: 1 << lbaf.lbads,
+        }
+    }
+}
+
+#[cfg(any(feature = "ioctl-nix", feature = "ioctl-rustix"))]
+impl Nvme {
+    /// Issue an Identify Namespace command for `nsid` and return the decoded
+    /// result. Most EC2 NVMe devices expose a single namespace with id
+    /// [`NVME_DEFAULT_NSID`].
+    pub fn identify_namespace<F: AsFd>(&self, fd: F, nsid: u32) -> Result<NvmeNamespace> {
+        identify_namespace(fd, nsid)
+    }
+
+    /// Open `path` and probe it, as with [`TryFrom<File>`].
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::try_from(File::open(path)?)
+    }
+
+    /// Scan `/dev` for NVMe namespace block devices (`nvme<ctrl>n<ns>`), probe
+    /// each one, and return its path paired with the probe result.
+    ///
+    /// Devices that are not recognized as Amazon EBS or instance store
+    /// volumes are reported as an [`Error`] alongside their path, rather than
+    /// aborting the whole scan.
+    pub fn discover() -> Result<Vec<(PathBuf, Result<Self>)>> {
+        let mut devices = Vec::new();
+        for entry in std::fs::read_dir("/dev")? {
+            let entry = entry?;
+            let name = entry.file_name();
+            if !is_nvme_namespace(&name.to_string_lossy()) {
+                continue;
+            }
+            let path = entry.path();
+            let result = File::open(&path)
+                .map_err(Error::from)
+                .and_then(Self::try_from);
+            devices.push((path, result));
+        }
+        Ok(devices)
+    }
+}
+
+/// Whether `name` looks like an NVMe namespace block device, e.g. `nvme0n1`,
+/// as opposed to a controller node (`nvme0`) or a partition (`nvme0n1p1`).
+#[cfg(any(feature = "ioctl-nix", feature = "ioctl-rustix"))]
+fn is_nvme_namespace(name: &str) -> bool {
+    let Some(rest) = name.strip_prefix("nvme") else {
+        return false;
+    };
+    let Some((ctrl, ns)) = rest.split_once('n') else {
+        return false;
+    };
+    !ctrl.is_empty()
+        && !ns.is_empty()
+        && ctrl.bytes().all(|b| b.is_ascii_digit())
+        && ns.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Resolve an Amazon device name (such as `sdf` or `xvdf`) or a virtual name
+/// (such as `ephemeral0`) to the path of the NVMe device that exposes it.
+#[cfg(any(feature = "ioctl-nix", feature = "ioctl-rustix"))]
+pub fn resolve(name: &str) -> Result<PathBuf> {
+    let target = device_suffix(name);
+    Nvme::discover()?
+        .into_iter()
+        .find_map(|(path, result)| {
+            let nvme = result.ok()?;
+            let names = &nvme.names;
+            names
+                .device_name
+                .iter()
+                .chain(names.virtual_name.iter())
+                .any(|n| device_suffix(n) == target)
+                .then_some(path)
+        })
+        .ok_or(Error::DeviceNameNotFound)
+}
+
+/// Strip a leading `/dev/` and an `sd`/`xvd` device name prefix, so that
+/// `sdf`, `xvdf`, and `/dev/xvdf` all compare equal. Virtual names such as
+/// `ephemeral0` have no such prefix and pass through unchanged.
+#[cfg(any(feature = "ioctl-nix", feature = "ioctl-rustix"))]
+fn device_suffix(name: &str) -> &str {
+    let name = name.strip_prefix("/dev/").unwrap_or(name);
+    name.strip_prefix("xvd")
+        .or_else(|| name.strip_prefix("sd"))
+        .unwrap_or(name)
+}
+
+/// Issue a Get Log Page command for the SMART/Health Information log on the
+/// given file descriptor and return the decoded result.
+#[cfg(feature = "ioctl-nix")]
+pub fn smart_log<F: AsFd>(fd: F) -> Result<SmartLog> {
+    Ok(ioctl_nix::nvme_get_log_page(fd)?.into())
+}
+
+/// Issue a Get Log Page command for the SMART/Health Information log on the
+/// given file descriptor and return the decoded result.
+#[cfg(feature = "ioctl-rustix")]
+pub fn smart_log<F: AsFd>(fd: F) -> Result<SmartLog> {
+    Ok(ioctl_rustix::nvme_get_log_page(fd)?.into())
+}
+
+/// Issue an Identify Namespace command for `nsid` on the given file
+/// descriptor and return the decoded result.
+#[cfg(feature = "ioctl-nix")]
+pub fn identify_namespace<F: AsFd>(fd: F, nsid: u32) -> Result<NvmeNamespace> {
+    Ok(ioctl_nix::nvme_identify_ns(fd, nsid)?.into())
+}
+
+/// Issue an Identify Namespace command for `nsid` on the given file
+/// descriptor and return the decoded result.
+#[cfg(feature = "ioctl-rustix")]
+pub fn identify_namespace<F: AsFd>(fd: F, nsid: u32) -> Result<NvmeNamespace> {
+    Ok(ioctl_rustix::nvme_identify_ns(fd, nsid)?.into())
+}
+
 #[cfg(all(feature = "ioctl-nix", feature = "ioctl-rustix"))]
 compile_error!("The features ioctl-nix and ioctl-rustix are mutually exclusive");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trim_padded_ascii_strips_nuls_and_spaces() {
+        let nul_padded = [b'A' as c_char, b'B' as c_char, 0, 0, 0];
+        assert_eq!(trim_padded_ascii(&nul_padded), "AB");
+
+        let space_padded = [b'A' as c_char, b'B' as c_char, b' ' as c_char, b' ' as c_char];
+        assert_eq!(trim_padded_ascii(&space_padded), "AB");
+
+        let mixed_padded = [b'A' as c_char, b'B' as c_char, b' ' as c_char, 0, 0];
+        assert_eq!(trim_padded_ascii(&mixed_padded), "AB");
+
+        let all_zero = [0 as c_char; 4];
+        assert_eq!(trim_padded_ascii(&all_zero), "");
+    }
+
+    #[test]
+    fn smart_log_page_is_512_bytes() {
+        assert_eq!(std::mem::size_of::<NvmeSmartLogPage>(), 512);
+    }
+
+    #[test]
+    fn smart_log_decodes_offsets() {
+        let raw = NvmeSmartLogPage {
+            critical_warning: 0x05,
+            composite_temp: 300u16.to_le_bytes(),
+            avail_spare: 90,
+            spare_thresh: 10,
+            percent_used: 42,
+            data_units_read: 1_000u128.to_le_bytes(),
+            data_units_written: 2_000u128.to_le_bytes(),
+            host_read_commands: 3_000u128.to_le_bytes(),
+            host_write_commands: 4_000u128.to_le_bytes(),
+            power_on_hours: 5_000u128.to_le_bytes(),
+            power_cycles: 6_000u128.to_le_bytes(),
+            unsafe_shutdowns: 7_000u128.to_le_bytes(),
+            media_errors: 8_000u128.to_le_bytes(),
+            ..NvmeSmartLogPage::default()
+        };
+
+        let log: SmartLog = raw.into();
+
+        assert_eq!(log.critical_warning, 0x05);
+        assert_eq!(log.temperature, 27);
+        assert_eq!(log.avail_spare, 90);
+        assert_eq!(log.spare_thresh, 10);
+        assert_eq!(log.percent_used, 42);
+        assert_eq!(log.data_units_read, 1_000);
+        assert_eq!(log.data_units_written, 2_000);
+        assert_eq!(log.host_read_commands, 3_000);
+        assert_eq!(log.host_write_commands, 4_000);
+        assert_eq!(log.power_on_hours, 5_000);
+        assert_eq!(log.power_cycles, 6_000);
+        assert_eq!(log.unsafe_shutdowns, 7_000);
+        assert_eq!(log.media_errors, 8_000);
+    }
+
+    #[cfg(any(feature = "ioctl-nix", feature = "ioctl-rustix"))]
+    #[test]
+    fn is_nvme_namespace_matches_namespace_nodes_only() {
+        assert!(is_nvme_namespace("nvme0n1"));
+        assert!(is_nvme_namespace("nvme12n34"));
+        assert!(!is_nvme_namespace("nvme0"));
+        assert!(!is_nvme_namespace("nvme0n1p1"));
+        assert!(!is_nvme_namespace("sda1"));
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn model_serializes_as_bare_variant_name() {
+        let json = serde_json::to_string(&Model::AmazonElasticBlockStore).unwrap();
+        assert_eq!(json, "\"AmazonElasticBlockStore\"");
+        let model: Model = serde_json::from_str(&json).unwrap();
+        assert!(matches!(model, Model::AmazonElasticBlockStore));
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn vendor_id_round_trips_as_plain_number() {
+        let vendor_id = VendorId(0x1D0F);
+        let json = serde_json::to_string(&vendor_id).unwrap();
+        assert_eq!(json, "7439");
+        let parsed: VendorId = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.0, 0x1D0F);
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn names_skips_internal_field() {
+        let names = Names {
+            device_name: Some("xvdf".to_string()),
+            virtual_name: None,
+            _internal: (),
+        };
+        let json = serde_json::to_string(&names).unwrap();
+        assert_eq!(json, r#"{"device_name":"xvdf","virtual_name":null}"#);
+    }
+
+    #[test]
+    fn namespace_decodes_capacity() {
+        let raw = NvmeIdNs {
+            nsze: 1_000_000,
+            ncap: 900_000,
+            nuse: 800_000,
+            flbas: 1,
+            lbaf: {
+                let mut lbaf = [NvmeLbaf::default(); 16];
+                lbaf[1] = NvmeLbaf {
+                    ms: 0,
+                    lbads: 12,
+                    rp: 0,
+                };
+                lbaf
+            },
+            ..NvmeIdNs::default()
+        };
+
+        let ns: NvmeNamespace = raw.into();
+
+        assert_eq!(ns.nsze, 1_000_000);
+        assert_eq!(ns.ncap, 900_000);
+        assert_eq!(ns.nuse, 800_000);
+        assert_eq!(ns.lba_data_size, 4096);
+        assert_eq!(ns.size_bytes(), 1_000_000 * 4096);
+    }
+
+    #[cfg(any(feature = "ioctl-nix", feature = "ioctl-rustix"))]
+    #[test]
+    fn device_suffix_normalizes_sd_and_xvd_prefixes() {
+        assert_eq!(device_suffix("/dev/xvdf"), "f");
+        assert_eq!(device_suffix("sdf"), "f");
+        assert_eq!(device_suffix("xvdf"), "f");
+        assert_eq!(device_suffix("ephemeral0"), "ephemeral0");
+    }
+}